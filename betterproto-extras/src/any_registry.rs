@@ -0,0 +1,38 @@
+//! Maps a `google.protobuf.Any` type URL to the betterproto message class
+//! that should be used to decode it, so generated modules can self-register
+//! at import time instead of the decoder needing static knowledge of every
+//! packed type.
+
+use crate::{betterproto_interop::BetterprotoMessageClass, Str};
+use pyo3::{pyfunction, types::PyType, Py, Python};
+use std::{collections::HashMap, sync::Mutex};
+
+static REGISTRY: Mutex<Option<HashMap<String, Py<PyType>>>> = Mutex::new(None);
+
+/// The `type_url` a packed message of type `full_name` is given, e.g.
+/// `type.googleapis.com/my.package.Foo`. Shared by the binary and JSON `Any`
+/// encoders so they always agree on the URL for a given message type.
+pub fn type_url_for(full_name: &Str) -> String {
+    format!("type.googleapis.com/{full_name}")
+}
+
+/// Registers `cls` as the target for `type_url`, replacing any previous
+/// registration for the same URL.
+#[pyfunction]
+pub fn register_any_type(type_url: String, cls: Py<PyType>) {
+    REGISTRY
+        .lock()
+        .expect("any-type registry was poisoned")
+        .get_or_insert_with(HashMap::new)
+        .insert(type_url, cls);
+}
+
+/// Looks up the class registered for `type_url`, if any.
+pub fn lookup(py: Python, type_url: &str) -> Option<BetterprotoMessageClass> {
+    REGISTRY
+        .lock()
+        .expect("any-type registry was poisoned")
+        .as_ref()?
+        .get(type_url)
+        .map(|cls| BetterprotoMessageClass::from_type(cls.clone_ref(py)))
+}
@@ -87,13 +87,23 @@ fn convert_value_type(
         ))),
         "message" => {
             let cls = msg_meta.get_class(field_name)?;
-            if cls.getattr("__module__")?.extract::<&str>()? == "datetime" {
+            let module = cls.getattr("__module__")?.extract::<&str>()?;
+            if module == "datetime" {
                 match cls.name()? {
                     "datetime" => return Ok(ProtoType::Timestamp),
                     "timedelta" => return Ok(ProtoType::Duration),
                     _ => {}
                 }
             }
+            if module == "betterproto.lib.google.protobuf" {
+                match cls.name()? {
+                    "Any" => return Ok(ProtoType::Any),
+                    "Struct" => return Ok(ProtoType::Struct),
+                    "Value" => return Ok(ProtoType::Value),
+                    "ListValue" => return Ok(ProtoType::ListValue),
+                    _ => {}
+                }
+            }
             Ok(ProtoType::CustomMessage(BetterprotoMessageClass(
                 cls.into_py(py),
             )))
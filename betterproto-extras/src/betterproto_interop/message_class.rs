@@ -8,6 +8,10 @@ use pyo3::{pyclass, types::PyType, FromPyObject, Py, PyCell, Python};
 pub struct BetterprotoMessageClass(pub(super) Py<PyType>);
 
 impl BetterprotoMessageClass {
+    pub fn from_type(cls: Py<PyType>) -> Self {
+        Self(cls)
+    }
+
     pub fn create_instance<'py>(
         &'py self,
         py: Python<'py>,
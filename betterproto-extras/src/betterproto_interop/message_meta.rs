@@ -18,6 +18,9 @@ pub struct BetterprotoMessageMeta<'py> {
     pub meta_by_field_name: &'py PyDict,
     pub oneof_group_by_field: HashMap<String, String>,
     pub default_gen: HashMap<String, &'py PyAny>,
+    /// Fully-qualified proto name (e.g. `my.package.Foo`), used for
+    /// `google.protobuf.Any` type URLs.
+    pub full_name: String,
 }
 
 impl<'py> BetterprotoMessageMeta<'py> {
@@ -47,6 +50,9 @@ impl<'py> BetterprotoMessageMeta<'py> {
                 Ok((meta.number, meta.into_descriptor(py, name.into(), &self)?))
             })
             .collect::<InteropResult<Vec<_>>>()?;
-        Ok(MessageDescriptor { fields })
+        Ok(MessageDescriptor {
+            full_name: self.full_name.into(),
+            fields,
+        })
     }
 }
@@ -27,4 +27,13 @@ impl BetterprotoEnumClass {
         })?;
         Ok(res)
     }
+
+    /// Looks up a member by its symbolic name, as accepted on proto3 JSON
+    /// input (e.g. the `"FOO_BAR"` in `{"status": "FOO_BAR"}`).
+    pub fn from_name(&self, py: Python, name: &str) -> InteropResult<Option<PyObject>> {
+        match self.0.as_ref(py).getattr(name) {
+            Ok(member) => Ok(Some(member.to_object(py))),
+            Err(_) => Ok(None),
+        }
+    }
 }
@@ -19,18 +19,22 @@ pub struct CustomMessageBuilder<'a, 'py> {
     fields: HashMap<u32, FieldBuilder<'a, 'py>>,
     active_groups: HashMap<Str, u32>,
     unknown_fields: Vec<u8>,
+    /// When set, reject a repeated appearance of a different oneof member
+    /// instead of silently keeping only the last one seen.
+    validate: bool,
 }
 
 impl<'a, 'py> CustomMessageBuilder<'a, 'py> {
-    pub fn new(py: Python<'py>, descriptor: &'a MessageDescriptor) -> Self {
+    pub fn with_validation(py: Python<'py>, descriptor: &'a MessageDescriptor, validate: bool) -> Self {
         Self {
             fields: descriptor
                 .fields
                 .iter()
-                .map(|(tag, descriptor)| (*tag, FieldBuilder::new(py, descriptor)))
+                .map(|(tag, descriptor)| (*tag, FieldBuilder::new(py, descriptor, validate)))
                 .collect(),
             active_groups: HashMap::new(),
             unknown_fields: Vec::new(),
+            validate,
         }
     }
 
@@ -93,7 +97,7 @@ impl MessageBuilder for CustomMessageBuilder<'_, '_> {
         let (tag, wire_type) = decode_key(buf)?;
         let group = match self.fields.get_mut(&tag) {
             Some(builder) => {
-                builder.parse_next(wire_type, buf)?;
+                builder.parse_next(tag, wire_type, buf)?;
                 builder.group()
             }
             None => {
@@ -104,6 +108,10 @@ impl MessageBuilder for CustomMessageBuilder<'_, '_> {
         if let Some(group) = group {
             if let Some(previous_tag) = self.active_groups.insert(group, tag) {
                 if previous_tag != tag {
+                    if self.validate {
+                        let field = self.fields.get(&tag).expect("Field exists").name();
+                        return Err(DecodeError::OneofConflict { field, tag });
+                    }
                     self.fields
                         .get_mut(&previous_tag)
                         .expect("Field exists")
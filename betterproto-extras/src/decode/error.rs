@@ -1,4 +1,5 @@
-use crate::betterproto_interop::InteropError;
+use crate::{betterproto_interop::InteropError, Str};
+use prost::encoding::WireType;
 use pyo3::{exceptions::PyRuntimeError, PyErr};
 use thiserror::Error;
 
@@ -10,10 +11,51 @@ pub enum DecodeError {
     ProstDecode(#[from] prost::DecodeError),
     #[error("The given binary data does not match the protobuf schema.")]
     MapEntryHasNoKey,
-    #[error("The given binary data does not match the protobuf schema.")]
-    InvalidMapEntryTag,
     #[error("The given binary data is not a valid protobuf message.")]
     InvalidData,
+    #[error("The length-delimited stream ended with a truncated record: the length prefix or its message body was cut short.")]
+    TruncatedDelimitedRecord,
+    #[error("Field `{field}` (tag {tag}) was set after another member of the same oneof.")]
+    OneofConflict { field: Str, tag: u32 },
+    #[error("field \"{path}\" (tag {tag}, wire type {wire_type:?}): {source}")]
+    WithPath {
+        path: String,
+        tag: u32,
+        wire_type: WireType,
+        #[source]
+        source: Box<DecodeError>,
+    },
+}
+
+impl DecodeError {
+    /// Prepends `segment` to the breadcrumb path of a nested decode failure,
+    /// creating the path on first use. Each recursing caller adds its own
+    /// segment (a field name, optionally suffixed with `[index]`) in front
+    /// of the one added below it, so a failure three messages deep reads as
+    /// `a.b.c` rather than just `c`. The `tag`/`wire_type` of the innermost
+    /// failure are kept; the ones passed by outer callers are only used the
+    /// first time a path is created.
+    pub fn with_path(self, segment: impl Into<String>, tag: u32, wire_type: WireType) -> Self {
+        match self {
+            DecodeError::WithPath {
+                path,
+                tag: inner_tag,
+                wire_type: inner_wire_type,
+                source,
+            } => DecodeError::WithPath {
+                path: format!("{}.{}", segment.into(), path),
+                tag: inner_tag,
+                wire_type: inner_wire_type,
+                source,
+            },
+            other => DecodeError::WithPath {
+                path: segment.into(),
+                tag,
+                wire_type,
+                source: Box::new(other),
+            },
+        }
+    }
 }
 
 pub type DecodeResult<T> = Result<T, DecodeError>;
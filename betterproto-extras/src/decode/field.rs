@@ -12,10 +12,10 @@ pub struct FieldBuilder<'a, 'py> {
 }
 
 impl<'a, 'py> FieldBuilder<'a, 'py> {
-    pub fn new(py: Python<'py>, descriptor: &'a FieldDescriptor) -> Self {
+    pub fn new(py: Python<'py>, descriptor: &'a FieldDescriptor, validate: bool) -> Self {
         Self {
             descriptor,
-            value: ValueBuilder::new(py, &descriptor.value_type),
+            value: ValueBuilder::new(py, &descriptor.value_type, validate),
         }
     }
 
@@ -32,17 +32,34 @@ impl<'a, 'py> FieldBuilder<'a, 'py> {
         }
     }
 
+    pub fn name(&self) -> Str {
+        self.descriptor.name.clone()
+    }
+
     pub fn reset(&mut self) {
         self.value.reset()
     }
 
-    pub fn parse_next(&mut self, wire_type: WireType, buf: &mut impl Buf) -> DecodeResult<()> {
+    /// Parses the next occurrence of this field, tagging any failure with
+    /// this field's name (and, for a repeated field, the index of the entry
+    /// being parsed) so the error reports where in the message it broke.
+    pub fn parse_next(&mut self, tag: u32, wire_type: WireType, buf: &mut impl Buf) -> DecodeResult<()> {
+        let name = self.descriptor.name.as_ref();
         match &self.descriptor.attribute {
-            FieldAttribute::Repeated => self.value.parse_next_list_entry(wire_type, buf)?,
-            FieldAttribute::Map(key_type) => {
-                self.value.parse_next_map_entry(wire_type, key_type, buf)?
+            FieldAttribute::Repeated => {
+                let index = self.value.repeated_len();
+                self.value
+                    .parse_next_list_entry(wire_type, buf)
+                    .map_err(|e| e.with_path(format!("{name}[{index}]"), tag, wire_type))?
             }
-            _ => self.value.parse_next_single(wire_type, buf)?,
+            FieldAttribute::Map(key_type) => self
+                .value
+                .parse_next_map_entry(wire_type, key_type, buf)
+                .map_err(|e| e.with_path(name, tag, wire_type))?,
+            _ => self
+                .value
+                .parse_next_single(wire_type, buf)
+                .map_err(|e| e.with_path(name, tag, wire_type))?,
         }
         Ok(())
     }
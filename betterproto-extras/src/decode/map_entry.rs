@@ -1,6 +1,9 @@
-use super::{value::ValueBuilder, DecodeError, DecodeResult, MessageBuilder};
+use super::{value::ValueBuilder, DecodeResult, MessageBuilder};
 use crate::descriptors::ProtoType;
-use prost::{bytes::Buf, encoding::decode_key};
+use prost::{
+    bytes::Buf,
+    encoding::{decode_key, skip_field, DecodeContext},
+};
 use pyo3::{PyObject, Python};
 
 pub struct MapEntryBuilder<'a, 'py> {
@@ -9,10 +12,15 @@ pub struct MapEntryBuilder<'a, 'py> {
 }
 
 impl<'a, 'py> MapEntryBuilder<'a, 'py> {
-    pub fn new(py: Python<'py>, key_type: &'a ProtoType, value_type: &'a ProtoType) -> Self {
+    pub fn new(
+        py: Python<'py>,
+        key_type: &'a ProtoType,
+        value_type: &'a ProtoType,
+        validate: bool,
+    ) -> Self {
         Self {
-            key: ValueBuilder::new(py, key_type),
-            value: ValueBuilder::new(py, value_type),
+            key: ValueBuilder::new(py, key_type, validate),
+            value: ValueBuilder::new(py, value_type, validate),
         }
     }
 
@@ -30,7 +38,11 @@ impl MessageBuilder for MapEntryBuilder<'_, '_> {
         match tag {
             1 => self.key.parse_next_single(wire_type, buf)?,
             2 => self.value.parse_next_single(wire_type, buf)?,
-            _ => Err(DecodeError::InvalidMapEntryTag)?,
+            // A map entry is just a regular two-field message, so a newer
+            // producer could reserve further tags on it; skip them instead
+            // of rejecting the whole payload, the same forward-compatible
+            // handling every other unknown field already gets.
+            _ => skip_field(wire_type, tag, buf, DecodeContext::default())?,
         }
         Ok(())
     }
@@ -13,9 +13,20 @@ use prost::{
 };
 
 pub fn merge_into_message(msg: BetterprotoMessage, buf: &mut impl Buf) -> DecodeResult<()> {
+    merge_into_message_validated(msg, buf, false)
+}
+
+/// Like [`merge_into_message`], but when `validate` is set, rejects a
+/// message that sets more than one member of the same oneof instead of
+/// silently keeping only the last one seen.
+pub fn merge_into_message_validated(
+    msg: BetterprotoMessage,
+    buf: &mut impl Buf,
+    validate: bool,
+) -> DecodeResult<()> {
     let py = msg.py();
     let cls = msg.class();
-    let mut builder = CustomMessageBuilder::new(py, cls.descriptor(py)?);
+    let mut builder = CustomMessageBuilder::with_validation(py, cls.descriptor(py)?, validate);
     while buf.has_remaining() {
         builder.parse_next_field(buf)?;
     }
@@ -2,11 +2,12 @@ use super::{
     custom_message::CustomMessageBuilder, map_entry::MapEntryBuilder, DecodeResult, MessageBuilder,
 };
 use crate::{
+    any_registry,
     betterproto_interop::InteropResult,
     descriptors::ProtoType,
     well_known_types::{
         BoolValue, BytesValue, DoubleValue, Duration, FloatValue, Int32Value, Int64Value,
-        StringValue, Timestamp, UInt32Value, UInt64Value,
+        ListValue, RawAny, StringValue, Struct, Timestamp, UInt32Value, UInt64Value, Value,
     },
 };
 use prost::{
@@ -22,9 +23,12 @@ use pyo3::{
 pub struct ValueBuilder<'a, 'py> {
     py: Python<'py>,
     proto_type: &'a ProtoType,
-    value: Value,
+    value: ValueState,
+    /// Forwarded into any nested `CustomMessageBuilder` so strict oneof
+    /// validation applies at every depth, not just the top-level message.
+    validate: bool,
 }
-enum Value {
+enum ValueState {
     Unset,
     Single(PyObject),
     Repeated(Vec<PyObject>),
@@ -32,25 +36,35 @@ enum Value {
 }
 
 impl<'a, 'py> ValueBuilder<'a, 'py> {
-    pub fn new(py: Python<'py>, proto_type: &'a ProtoType) -> Self {
+    pub fn new(py: Python<'py>, proto_type: &'a ProtoType, validate: bool) -> Self {
         ValueBuilder {
             py,
             proto_type,
-            value: Value::Unset,
+            value: ValueState::Unset,
+            validate,
         }
     }
 
     pub fn reset(&mut self) {
-        self.value = Value::Unset;
+        self.value = ValueState::Unset;
+    }
+
+    /// The number of entries parsed into this field so far, used to label
+    /// the next repeated entry's position in a decode error's path.
+    pub fn repeated_len(&self) -> usize {
+        match &self.value {
+            ValueState::Repeated(ls) => ls.len(),
+            _ => 0,
+        }
     }
 
     pub fn into_object(self) -> Option<PyObject> {
         let py = self.py;
         match self.value {
-            Value::Unset => None,
-            Value::Single(obj) => Some(obj),
-            Value::Repeated(ls) => Some(ls.to_object(py)),
-            Value::Map(ls) => Some(ls.into_py_dict(py).to_object(py)),
+            ValueState::Unset => None,
+            ValueState::Single(obj) => Some(obj),
+            ValueState::Repeated(ls) => Some(ls.to_object(py)),
+            ValueState::Map(ls) => Some(ls.into_py_dict(py).to_object(py)),
         }
     }
 
@@ -59,7 +73,13 @@ impl<'a, 'py> ValueBuilder<'a, 'py> {
         wire_type: WireType,
         buf: &mut impl Buf,
     ) -> DecodeResult<()> {
-        self.set_single(parse_next_value(self.py, self.proto_type, wire_type, buf)?);
+        self.set_single(parse_next_value(
+            self.py,
+            self.proto_type,
+            self.validate,
+            wire_type,
+            buf,
+        )?);
         Ok(())
     }
 
@@ -74,7 +94,7 @@ impl<'a, 'py> ValueBuilder<'a, 'py> {
                 return Ok(());
             }
         }
-        let obj = parse_next_value(self.py, self.proto_type, wire_type, buf)?;
+        let obj = parse_next_value(self.py, self.proto_type, self.validate, wire_type, buf)?;
         self.push_repeated(obj);
         Ok(())
     }
@@ -85,7 +105,7 @@ impl<'a, 'py> ValueBuilder<'a, 'py> {
         key_type: &ProtoType,
         buf: &mut impl Buf,
     ) -> DecodeResult<()> {
-        let mut builder = MapEntryBuilder::new(self.py, key_type, self.proto_type);
+        let mut builder = MapEntryBuilder::new(self.py, key_type, self.proto_type, self.validate);
         builder.parse_next_length_delimited(wire_type, buf)?;
         self.push_map_entry(builder.into_tuple()?);
         Ok(())
@@ -93,29 +113,29 @@ impl<'a, 'py> ValueBuilder<'a, 'py> {
 
     fn set_single(&mut self, obj: PyObject) {
         match &mut self.value {
-            Value::Single(x) => *x = obj,
-            _ => self.value = Value::Single(obj),
+            ValueState::Single(x) => *x = obj,
+            _ => self.value = ValueState::Single(obj),
         }
     }
 
     fn push_repeated(&mut self, obj: PyObject) {
         match &mut self.value {
-            Value::Repeated(ls) => ls.push(obj),
-            _ => self.value = Value::Repeated(vec![obj]),
+            ValueState::Repeated(ls) => ls.push(obj),
+            _ => self.value = ValueState::Repeated(vec![obj]),
         }
     }
 
     fn append_repeated(&mut self, mut objs: Vec<PyObject>) {
         match &mut self.value {
-            Value::Repeated(ls) => ls.append(&mut objs),
-            _ => self.value = Value::Repeated(objs),
+            ValueState::Repeated(ls) => ls.append(&mut objs),
+            _ => self.value = ValueState::Repeated(objs),
         }
     }
 
     fn push_map_entry(&mut self, obj: (PyObject, PyObject)) {
         match &mut self.value {
-            Value::Map(ls) => ls.push(obj),
-            _ => self.value = Value::Map(vec![obj]),
+            ValueState::Map(ls) => ls.push(obj),
+            _ => self.value = ValueState::Map(vec![obj]),
         }
     }
 }
@@ -123,6 +143,7 @@ impl<'a, 'py> ValueBuilder<'a, 'py> {
 fn parse_next_value(
     py: Python,
     proto_type: &ProtoType,
+    validate: bool,
     wire_type: WireType,
     buf: &mut impl Buf,
 ) -> DecodeResult<PyObject> {
@@ -209,7 +230,8 @@ fn parse_next_value(
             Ok(cls.create_instance(py, value)?)
         }
         ProtoType::CustomMessage(cls) => {
-            let mut builder = CustomMessageBuilder::new(py, cls.descriptor(py)?);
+            let mut builder =
+                CustomMessageBuilder::with_validation(py, cls.descriptor(py)?, validate);
             builder.parse_next_length_delimited(wire_type, buf)?;
             let msg = cls.create_instance(py)?;
             builder.merge_into(msg)?;
@@ -226,6 +248,21 @@ fn parse_next_value(
         ProtoType::StringValue => Ok(StringValue::decode_length_delimited(buf)?.to_object(py)),
         ProtoType::Timestamp => Ok(Timestamp::decode_length_delimited(buf)?.to_object(py)),
         ProtoType::Duration => Ok(Duration::decode_length_delimited(buf)?.to_object(py)),
+        ProtoType::Any => {
+            let raw = RawAny::decode_length_delimited(buf)?;
+            match any_registry::lookup(py, &raw.type_url) {
+                Some(cls) => {
+                    let msg = cls.create_instance(py)?;
+                    let mut inner = raw.value.as_slice();
+                    super::merge_into_message_validated(msg, &mut inner, validate)?;
+                    Ok(msg.to_object(py))
+                }
+                None => Ok(raw.to_object(py)),
+            }
+        }
+        ProtoType::Struct => Ok(Struct::decode_length_delimited(buf)?.to_object(py)),
+        ProtoType::Value => Ok(Value::decode_length_delimited(buf)?.to_object(py)),
+        ProtoType::ListValue => Ok(ListValue::decode_length_delimited(buf)?.to_object(py)),
     }
 }
 
@@ -0,0 +1,75 @@
+//! Length-delimited message framing: a varint byte-length prefix followed by
+//! that many bytes of an encoded message, repeated back to back. This is the
+//! framing `writeDelimitedTo`/`parseDelimitedFrom` use for concatenated
+//! records (log files, piped gRPC frames, ...). A buffer that stops exactly
+//! on a frame boundary decodes cleanly; one that stops mid-frame raises
+//! [`DecodeError::TruncatedDelimitedRecord`].
+
+use crate::{
+    betterproto_interop::{BetterprotoMessage, BetterprotoMessageClass, InteropError},
+    decode::{merge_into_message, DecodeError, DecodeResult},
+    encode::{EncodeResult, MessageEncoder},
+};
+use prost::encoding::{decode_varint, encode_varint};
+use pyo3::{intern, types::PyBytes, PyAny, PyObject, Python, ToPyObject};
+
+/// Reads an in-memory buffer or a file-like object exposing `read`, always
+/// returning an owned byte buffer.
+fn read_all(source: &PyAny) -> DecodeResult<Vec<u8>> {
+    if let Ok(bytes) = source.extract::<Vec<u8>>() {
+        return Ok(bytes);
+    }
+    let py = source.py();
+    let data = source
+        .call_method0(intern!(py, "read"))
+        .map_err(InteropError::from)?;
+    data.extract().map_err(|e| InteropError::from(e).into())
+}
+
+pub fn deserialize_delimited(
+    py: Python,
+    cls: BetterprotoMessageClass,
+    source: &PyAny,
+) -> DecodeResult<Vec<PyObject>> {
+    let data = read_all(source)?;
+    let mut buf: &[u8] = &data;
+    let mut messages = Vec::new();
+
+    while !buf.is_empty() {
+        // An empty buffer is a clean end of stream, but any non-empty
+        // leftover that isn't a full frame (a partial length prefix, or a
+        // length prefix whose body got cut short) is corrupt input, not a
+        // legitimate stopping point, so it's reported rather than swallowed.
+        let mut probe = buf;
+        let len = match decode_varint(&mut probe) {
+            Ok(len) => len as usize,
+            Err(_) => return Err(DecodeError::TruncatedDelimitedRecord),
+        };
+        if len > probe.len() {
+            return Err(DecodeError::TruncatedDelimitedRecord);
+        }
+        let (record, rest) = probe.split_at(len);
+        buf = rest;
+
+        let msg = cls.create_instance(py)?;
+        let mut record = record;
+        merge_into_message(msg, &mut record)?;
+        messages.push(msg.to_object(py));
+    }
+
+    Ok(messages)
+}
+
+pub fn serialize_delimited<'py>(
+    py: Python<'py>,
+    messages: Vec<BetterprotoMessage>,
+) -> EncodeResult<&'py PyBytes> {
+    let mut buf = Vec::new();
+    for msg in messages {
+        let cls = msg.class();
+        let encoded = MessageEncoder::from_betterproto_msg(msg, cls.descriptor(py)?)?.into_vec();
+        encode_varint(encoded.len() as u64, &mut buf);
+        buf.extend_from_slice(&encoded);
+    }
+    Ok(PyBytes::new(py, &buf))
+}
@@ -1,16 +1,19 @@
 use pyo3::{
-    types::{PyBytes, PyString},
+    types::{PyBytes, PyDict, PyList, PyString},
     PyObject, Python, ToPyObject,
 };
 
 use crate::{
     betterproto_interop::{BetterprotoEnumClass, BetterprotoMessageClass, InteropResult},
-    well_known_types::{Duration, Timestamp},
+    well_known_types::{Duration, RawAny, Timestamp},
     Str,
 };
 
 #[derive(Debug)]
 pub struct MessageDescriptor {
+    /// The fully-qualified proto name (e.g. `my.package.Foo`), used to build
+    /// and resolve `google.protobuf.Any` type URLs.
+    pub full_name: Str,
     pub fields: Vec<(u32, FieldDescriptor)>,
 }
 
@@ -60,6 +63,10 @@ pub enum ProtoType {
     StringValue,
     Duration,
     Timestamp,
+    Any,
+    Struct,
+    Value,
+    ListValue,
 }
 
 impl ProtoType {
@@ -92,6 +99,10 @@ impl ProtoType {
             | Self::UInt64Value => Ok(py.None()),
             Self::Timestamp => Ok(Timestamp::default().to_object(py)),
             Self::Duration => Ok(Duration::default().to_object(py)),
+            Self::Any => Ok(RawAny::default().to_object(py)),
+            Self::Struct => Ok(PyDict::new(py).to_object(py)),
+            Self::Value => Ok(py.None()),
+            Self::ListValue => Ok(PyList::empty(py).to_object(py)),
         }
     }
 }
@@ -1,10 +1,10 @@
 use super::{chunk::Chunk, EncodeResult};
 use crate::{
-    betterproto_interop::BetterprotoMessage,
+    any_registry, betterproto_interop::BetterprotoMessage,
     descriptors::{FieldAttribute, FieldDescriptor, MessageDescriptor, ProtoType},
     well_known_types::{
         BoolValue, BytesValue, DoubleValue, Duration, FloatValue, Int32Value, Int64Value,
-        StringValue, Timestamp, UInt32Value, UInt64Value,
+        ListValue, RawAny, StringValue, Struct, Timestamp, UInt32Value, UInt64Value, Value,
     },
 };
 use prost::{encoding as enc, Message};
@@ -14,20 +14,56 @@ use pyo3::{
     PyAny, PyResult,
 };
 
-pub struct MessageEncoder(Vec<Chunk>);
+/// Sort key for a decoded-from-Python map entry, used only in canonical
+/// mode. All entries of a given map field share a variant, since the key
+/// type is fixed per field.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum MapSortKey {
+    Int(i128),
+    Str(String),
+}
+
+pub struct MessageEncoder {
+    /// Tag paired with each chunk so canonical mode can sort by ascending
+    /// tag number. Unknown fields are tagged `u32::MAX` so they always sort
+    /// last, after every known field.
+    chunks: Vec<(u32, Chunk)>,
+    canonical: bool,
+}
 
 impl MessageEncoder {
     pub fn from_betterproto_msg(
         msg: BetterprotoMessage,
         descriptor: &MessageDescriptor,
     ) -> EncodeResult<Self> {
-        let mut encoder = MessageEncoder::new();
+        Self::build(msg, descriptor, false)
+    }
+
+    /// Like [`Self::from_betterproto_msg`], but emits fields in ascending
+    /// tag order and sorts map entries by key, so that two semantically
+    /// equal messages always produce identical bytes.
+    pub fn from_betterproto_msg_canonical(
+        msg: BetterprotoMessage,
+        descriptor: &MessageDescriptor,
+    ) -> EncodeResult<Self> {
+        Self::build(msg, descriptor, true)
+    }
+
+    fn build(
+        msg: BetterprotoMessage,
+        descriptor: &MessageDescriptor,
+        canonical: bool,
+    ) -> EncodeResult<Self> {
+        let mut encoder = MessageEncoder::new(canonical);
         for (tag, field) in descriptor.fields.iter() {
             if let Some(value) = msg.get_field(&field.name)? {
                 encoder.load_field(*tag, field, value)?;
             }
         }
         encoder.load_unknown_fields(msg.get_unknown_fields()?);
+        if canonical {
+            encoder.chunks.sort_by_key(|(tag, _)| *tag);
+        }
         Ok(encoder)
     }
 
@@ -39,25 +75,28 @@ impl MessageEncoder {
         buf
     }
 
-    fn new() -> Self {
-        Self(vec![])
+    fn new(canonical: bool) -> Self {
+        Self {
+            chunks: vec![],
+            canonical,
+        }
     }
 
     pub(super) fn encoded_len(&self) -> usize {
-        self.0
+        self.chunks
             .iter()
-            .map(|chunk| chunk.encoded_len())
+            .map(|(_, chunk)| chunk.encoded_len())
             .sum::<usize>()
     }
 
     pub(super) fn encode(&self, buf: &mut Vec<u8>) {
-        for chunk in self.0.iter() {
+        for (_, chunk) in self.chunks.iter() {
             chunk.encode(buf);
         }
     }
 
     fn load_unknown_fields(&mut self, unknowns: Vec<u8>) {
-        self.0.push(Chunk::from_encoded(unknowns))
+        self.chunks.push((u32::MAX, Chunk::from_encoded(unknowns)))
     }
 
     fn load_field(
@@ -75,8 +114,21 @@ impl MessageEncoder {
                 }
             }
             FieldAttribute::Map(key_type) => {
-                for (key, value) in value.downcast::<PyDict>()?.iter() {
-                    self.load_map_entry(tag, key_type, &descriptor.value_type, key, value)?;
+                let dict = value.downcast::<PyDict>()?;
+                if self.canonical {
+                    let mut entries = dict
+                        .iter()
+                        .map(|(key, value)| {
+                            self.build_map_entry(tag, key_type, &descriptor.value_type, key, value)
+                        })
+                        .collect::<EncodeResult<Vec<_>>>()?;
+                    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                    self.chunks
+                        .extend(entries.into_iter().map(|(_, chunk)| (tag, chunk)));
+                } else {
+                    for (key, value) in dict.iter() {
+                        self.load_map_entry(tag, key_type, &descriptor.value_type, key, value)?;
+                    }
                 }
             }
             FieldAttribute::None => self.load_single::<true>(tag, &descriptor.value_type, value)?,
@@ -224,9 +276,12 @@ impl MessageEncoder {
                 if SKIP_DEFAULT && !msg.should_be_serialized()? {
                     return Ok(());
                 }
+                // Carry `self.canonical` down so a canonical top-level
+                // encode doesn't leave nested messages' field and map-entry
+                // order non-deterministic.
                 Chunk::from_message(
                     tag,
-                    MessageEncoder::from_betterproto_msg(msg, cls.descriptor(py)?)?,
+                    MessageEncoder::build(msg, cls.descriptor(py)?, self.canonical)?,
                 )
             }
             ProtoType::BoolValue => Chunk::from_known_message::<BoolValue>(tag, value.extract()?)?,
@@ -268,9 +323,59 @@ impl MessageEncoder {
                 }
                 Chunk::from_known_message(tag, msg)?
             }
+            // An Any decoded with an unregistered type_url comes back as a
+            // plain RawAny rather than a betterproto message (see
+            // decode/value.rs), since there's no class to pack it back into.
+            // Re-emit its stored type_url/value bytes verbatim in that case
+            // instead of assuming every Any field holds a packed message.
+            ProtoType::Any if value.extract::<RawAny>().is_ok() => {
+                let raw = value.extract::<RawAny>()?;
+                if SKIP_DEFAULT && raw.type_url.is_empty() && raw.value.is_empty() {
+                    return Ok(());
+                }
+                Chunk::from_known_message(tag, raw)?
+            }
+            ProtoType::Any => {
+                let msg: BetterprotoMessage = value.extract()?;
+                if SKIP_DEFAULT && !msg.should_be_serialized()? {
+                    return Ok(());
+                }
+                let inner_cls = msg.class();
+                let inner_descriptor = inner_cls.descriptor(py)?;
+                let type_url = any_registry::type_url_for(&inner_descriptor.full_name);
+                let packed = MessageEncoder::build(msg, inner_descriptor, self.canonical)?.into_vec();
+                Chunk::from_known_message(
+                    tag,
+                    RawAny {
+                        type_url,
+                        value: packed,
+                    },
+                )?
+            }
+            ProtoType::Struct => {
+                let value: Struct = value.extract()?;
+                if SKIP_DEFAULT && value.fields.is_empty() {
+                    return Ok(());
+                }
+                Chunk::from_known_message(tag, value)?
+            }
+            ProtoType::Value => {
+                let value: Value = value.extract()?;
+                if SKIP_DEFAULT && value.kind.is_none() {
+                    return Ok(());
+                }
+                Chunk::from_known_message(tag, value)?
+            }
+            ProtoType::ListValue => {
+                let value: ListValue = value.extract()?;
+                if SKIP_DEFAULT && value.values.is_empty() {
+                    return Ok(());
+                }
+                Chunk::from_known_message(tag, value)?
+            }
         };
 
-        self.0.push(chunk);
+        self.chunks.push((tag, chunk));
         Ok(())
     }
 
@@ -379,7 +484,7 @@ impl MessageEncoder {
 
         match chunk {
             Some(chunk) => {
-                self.0.push(chunk);
+                self.chunks.push((tag, chunk));
                 Ok(true)
             }
             _ => Ok(false),
@@ -394,10 +499,42 @@ impl MessageEncoder {
         key: &PyAny,
         value: &PyAny,
     ) -> EncodeResult<()> {
-        let mut encoder = MessageEncoder::new();
-        encoder.load_single::<true>(1, key_type, key)?;
-        encoder.load_single::<true>(2, value_type, value)?;
-        self.0.push(Chunk::from_message(tag, encoder));
+        let (_, chunk) = self.build_map_entry(tag, key_type, value_type, key, value)?;
+        self.chunks.push((tag, chunk));
         Ok(())
     }
+
+    fn build_map_entry(
+        &self,
+        tag: u32,
+        key_type: &ProtoType,
+        value_type: &ProtoType,
+        key: &PyAny,
+        value: &PyAny,
+    ) -> EncodeResult<(MapSortKey, Chunk)> {
+        // Propagate canonical mode into the entry's own encoder so a
+        // message-typed map value is itself sorted deterministically.
+        let mut entry = MessageEncoder::new(self.canonical);
+        entry.load_single::<true>(1, key_type, key)?;
+        entry.load_single::<true>(2, value_type, value)?;
+        let sort_key = map_sort_key(key_type, key)?;
+        Ok((sort_key, Chunk::from_message(tag, entry)))
+    }
+}
+
+fn map_sort_key(key_type: &ProtoType, key: &PyAny) -> EncodeResult<MapSortKey> {
+    let key = match key_type {
+        ProtoType::Bool => MapSortKey::Int(key.extract::<bool>()? as i128),
+        ProtoType::String => MapSortKey::Str(key.extract()?),
+        ProtoType::Int32 | ProtoType::Sint32 | ProtoType::Fixed32 | ProtoType::Sfixed32 => {
+            MapSortKey::Int(key.extract::<i32>()? as i128)
+        }
+        ProtoType::Uint32 => MapSortKey::Int(key.extract::<u32>()? as i128),
+        ProtoType::Int64 | ProtoType::Sint64 | ProtoType::Sfixed64 => {
+            MapSortKey::Int(key.extract::<i64>()? as i128)
+        }
+        ProtoType::Uint64 | ProtoType::Fixed64 => MapSortKey::Int(key.extract::<u64>()? as i128),
+        _ => MapSortKey::Str(key.str()?.extract()?),
+    };
+    Ok(key)
 }
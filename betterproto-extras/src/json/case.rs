@@ -0,0 +1,17 @@
+/// Converts a proto field name (snake_case, as stored on `FieldDescriptor`)
+/// into the lowerCamelCase form used by canonical proto3 JSON.
+pub fn to_lower_camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut capitalize_next = false;
+    for ch in name.chars() {
+        if ch == '_' {
+            capitalize_next = true;
+        } else if capitalize_next {
+            out.extend(ch.to_uppercase());
+            capitalize_next = false;
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
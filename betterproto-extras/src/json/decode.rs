@@ -0,0 +1,249 @@
+use super::{
+    case::to_lower_camel_case,
+    error::{JsonError, JsonResult},
+};
+use crate::{
+    any_registry,
+    betterproto_interop::BetterprotoMessage,
+    descriptors::{FieldAttribute, FieldDescriptor, ProtoType},
+    well_known_types::{Duration, RawAny, Timestamp},
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use pyo3::{
+    types::{PyBytes, PyDict},
+    PyObject, Python, ToPyObject,
+};
+use serde_json::{Map, Value};
+
+pub fn merge_json_into_message(msg: BetterprotoMessage, value: &Value) -> JsonResult<()> {
+    let py = msg.py();
+    let obj = match value {
+        Value::Object(obj) => obj,
+        _ => return Err(JsonError::TypeMismatch("$".to_string())),
+    };
+    let cls = msg.class();
+    let descriptor = cls.descriptor(py)?;
+    for (_, field) in descriptor.fields.iter() {
+        if let Some(json_value) = lookup_field(obj, &field.name) {
+            if let Some(py_value) = field_from_json(py, field, json_value)? {
+                msg.set_field(&field.name, py_value)?;
+            }
+        }
+    }
+    msg.set_deserialized()?;
+    Ok(())
+}
+
+/// Looks a field up by either its camelCase JSON name or its original
+/// snake_case proto name, matching the canonical mapping's input leniency.
+fn lookup_field<'a>(obj: &'a Map<String, Value>, field_name: &str) -> Option<&'a Value> {
+    obj.get(&to_lower_camel_case(field_name))
+        .or_else(|| obj.get(field_name))
+}
+
+fn field_from_json(
+    py: Python,
+    field: &FieldDescriptor,
+    value: &Value,
+) -> JsonResult<Option<PyObject>> {
+    match &field.attribute {
+        // The canonical mapping treats `null` for a repeated or map field as
+        // the field being unset, same as omitting it entirely, rather than
+        // a type error.
+        FieldAttribute::Repeated if value.is_null() => Ok(None),
+        FieldAttribute::Repeated => {
+            let items = value
+                .as_array()
+                .ok_or_else(|| JsonError::TypeMismatch(field.name.to_string()))?
+                .iter()
+                .map(|item| value_from_json(py, &field.value_type, item))
+                .collect::<JsonResult<Vec<_>>>()?;
+            Ok(Some(items.to_object(py)))
+        }
+        FieldAttribute::Map(_) if value.is_null() => Ok(None),
+        FieldAttribute::Map(key_type) => {
+            let obj = value
+                .as_object()
+                .ok_or_else(|| JsonError::TypeMismatch(field.name.to_string()))?;
+            let dict = pyo3::types::PyDict::new(py);
+            for (key, value) in obj.iter() {
+                let key = map_key_from_json(py, key_type, key)?;
+                let value = value_from_json(py, &field.value_type, value)?;
+                dict.set_item(key, value)?;
+            }
+            Ok(Some(dict.to_object(py)))
+        }
+        FieldAttribute::Optional | FieldAttribute::Group(_) => {
+            if value.is_null() {
+                return Ok(Some(py.None()));
+            }
+            Ok(Some(value_from_json(py, &field.value_type, value)?))
+        }
+        FieldAttribute::None => {
+            if value.is_null() {
+                return Ok(None);
+            }
+            Ok(Some(value_from_json(py, &field.value_type, value)?))
+        }
+    }
+}
+
+fn value_from_json(py: Python, proto_type: &ProtoType, value: &Value) -> JsonResult<PyObject> {
+    let mismatch = || JsonError::TypeMismatch(format!("{proto_type:?}"));
+    match proto_type {
+        ProtoType::Bool | ProtoType::BoolValue => {
+            Ok(value.as_bool().ok_or_else(mismatch)?.to_object(py))
+        }
+        ProtoType::Bytes | ProtoType::BytesValue => {
+            let raw = value.as_str().ok_or_else(mismatch)?;
+            let bytes = BASE64.decode(raw).map_err(|_| mismatch())?;
+            Ok(PyBytes::new(py, &bytes).to_object(py))
+        }
+        ProtoType::Double | ProtoType::Float | ProtoType::DoubleValue | ProtoType::FloatValue => {
+            Ok(number_from_json(value)?.to_object(py))
+        }
+        ProtoType::Int32 | ProtoType::Sint32 | ProtoType::Fixed32 | ProtoType::Sfixed32 => {
+            Ok(integer_from_json::<i32>(value)?.to_object(py))
+        }
+        ProtoType::Uint32 => Ok(integer_from_json::<u32>(value)?.to_object(py)),
+        ProtoType::Int32Value => Ok(integer_from_json::<i32>(value)?.to_object(py)),
+        ProtoType::UInt32Value => Ok(integer_from_json::<u32>(value)?.to_object(py)),
+        ProtoType::Int64
+        | ProtoType::Sint64
+        | ProtoType::Sfixed64
+        | ProtoType::Int64Value => Ok(integer_from_json::<i64>(value)?.to_object(py)),
+        ProtoType::Uint64 | ProtoType::Fixed64 | ProtoType::UInt64Value => {
+            Ok(integer_from_json::<u64>(value)?.to_object(py))
+        }
+        ProtoType::String | ProtoType::StringValue => {
+            Ok(value.as_str().ok_or_else(mismatch)?.to_object(py))
+        }
+        ProtoType::Enum(cls) => {
+            let member = match value {
+                Value::String(name) => cls.from_name(py, name)?,
+                Value::Number(_) => None,
+                _ => return Err(mismatch()),
+            };
+            match member {
+                Some(member) => Ok(member),
+                None => {
+                    let raw = value.as_i64().ok_or_else(mismatch)? as i32;
+                    Ok(cls.create_instance(py, raw)?)
+                }
+            }
+        }
+        ProtoType::CustomMessage(cls) => {
+            let msg = cls.create_instance(py)?;
+            merge_json_into_message(msg, value)?;
+            Ok(msg.to_object(py))
+        }
+        ProtoType::Timestamp => {
+            let raw = value.as_str().ok_or_else(mismatch)?;
+            Ok(Timestamp::from_rfc3339(py, raw)
+                .map_err(|_| mismatch())?
+                .to_object(py))
+        }
+        ProtoType::Duration => {
+            let raw = value.as_str().ok_or_else(mismatch)?;
+            Ok(Duration::from_json_string(raw)
+                .map_err(|_| mismatch())?
+                .to_object(py))
+        }
+        ProtoType::Any => {
+            let obj = value.as_object().ok_or_else(mismatch)?;
+            let type_url = obj
+                .get("@type")
+                .and_then(Value::as_str)
+                .ok_or_else(mismatch)?;
+            match any_registry::lookup(py, type_url) {
+                Some(cls) => {
+                    let msg = cls.create_instance(py)?;
+                    merge_json_into_message(msg, value)?;
+                    Ok(msg.to_object(py))
+                }
+                // Without a registered class we can't recover the original
+                // wire bytes from the flattened JSON shape; keep what we can.
+                None => Ok(RawAny {
+                    type_url: type_url.to_string(),
+                    value: Vec::new(),
+                }
+                .to_object(py)),
+            }
+        }
+        ProtoType::Struct | ProtoType::Value | ProtoType::ListValue => {
+            dynamic_value_from_json(py, value)
+        }
+    }
+}
+
+/// Converts a proto3 JSON value directly into the native `dict`/`list`/
+/// scalar Python representation backing a `Struct`/`Value`/`ListValue`
+/// field, the inverse of `dynamic_value_to_json`.
+fn dynamic_value_from_json(py: Python, value: &Value) -> JsonResult<PyObject> {
+    let res = match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => b.to_object(py),
+        Value::Number(_) => number_from_json(value)?.to_object(py),
+        Value::String(s) => s.to_object(py),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| dynamic_value_from_json(py, item))
+            .collect::<JsonResult<Vec<_>>>()?
+            .to_object(py),
+        Value::Object(obj) => {
+            let dict = PyDict::new(py);
+            for (key, value) in obj.iter() {
+                dict.set_item(key, dynamic_value_from_json(py, value)?)?;
+            }
+            dict.to_object(py)
+        }
+    };
+    Ok(res)
+}
+
+fn number_from_json(value: &Value) -> JsonResult<f64> {
+    match value {
+        Value::Number(n) => n.as_f64().ok_or_else(|| JsonError::TypeMismatch("number".into())),
+        Value::String(s) => match s.as_str() {
+            "NaN" => Ok(f64::NAN),
+            "Infinity" => Ok(f64::INFINITY),
+            "-Infinity" => Ok(f64::NEG_INFINITY),
+            s => s.parse().map_err(|_| JsonError::TypeMismatch("number".into())),
+        },
+        _ => Err(JsonError::TypeMismatch("number".into())),
+    }
+}
+
+fn integer_from_json<T>(value: &Value) -> JsonResult<T>
+where
+    T: std::str::FromStr + TryFrom<i64>,
+{
+    match value {
+        Value::Number(n) => n
+            .as_i64()
+            .and_then(|n| T::try_from(n).ok())
+            .ok_or_else(|| JsonError::TypeMismatch("integer".into())),
+        Value::String(s) => s.parse().map_err(|_| JsonError::TypeMismatch("integer".into())),
+        _ => Err(JsonError::TypeMismatch("integer".into())),
+    }
+}
+
+fn map_key_from_json(py: Python, key_type: &ProtoType, key: &str) -> JsonResult<PyObject> {
+    let mismatch = || JsonError::InvalidMapKey(key.to_string());
+    let obj = match key_type {
+        ProtoType::Bool => (key == "true").to_object(py),
+        ProtoType::String => key.to_object(py),
+        ProtoType::Int32 | ProtoType::Sint32 | ProtoType::Fixed32 | ProtoType::Sfixed32 => {
+            key.parse::<i32>().map_err(|_| mismatch())?.to_object(py)
+        }
+        ProtoType::Uint32 => key.parse::<u32>().map_err(|_| mismatch())?.to_object(py),
+        ProtoType::Int64 | ProtoType::Sint64 | ProtoType::Sfixed64 => {
+            key.parse::<i64>().map_err(|_| mismatch())?.to_object(py)
+        }
+        ProtoType::Uint64 | ProtoType::Fixed64 => {
+            key.parse::<u64>().map_err(|_| mismatch())?.to_object(py)
+        }
+        _ => return Err(mismatch()),
+    };
+    Ok(obj)
+}
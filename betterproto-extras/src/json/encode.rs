@@ -0,0 +1,254 @@
+use super::{
+    case::to_lower_camel_case,
+    error::{JsonError, JsonResult},
+};
+use crate::{
+    any_registry,
+    betterproto_interop::BetterprotoMessage,
+    descriptors::{FieldAttribute, FieldDescriptor, MessageDescriptor, ProtoType},
+    well_known_types::{Duration, RawAny, Timestamp},
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use prost::Message;
+use pyo3::{
+    intern,
+    types::{PyBool, PyDict, PyList},
+    PyAny,
+};
+use serde_json::{Map, Number, Value};
+
+/// Encodes `msg` using the canonical proto3 JSON mapping, driven by the same
+/// `MessageDescriptor` the binary codec in `encode::message` uses, so the
+/// two paths can never disagree about a field's name, type, or attribute.
+pub fn message_to_json(msg: BetterprotoMessage, descriptor: &MessageDescriptor) -> JsonResult<Value> {
+    let mut obj = Map::with_capacity(descriptor.fields.len());
+    for (_, field) in descriptor.fields.iter() {
+        if let Some(value) = msg.get_field(&field.name)? {
+            if let Some(json_value) = field_to_json(field, value)? {
+                obj.insert(to_lower_camel_case(&field.name), json_value);
+            }
+        }
+    }
+    Ok(Value::Object(obj))
+}
+
+fn field_to_json(field: &FieldDescriptor, value: &PyAny) -> JsonResult<Option<Value>> {
+    match &field.attribute {
+        FieldAttribute::Repeated => {
+            let items = value
+                .downcast::<PyList>()
+                .map_err(|_| JsonError::TypeMismatch(field.name.to_string()))?
+                .iter()
+                .map(|item| value_to_json(&field.value_type, item))
+                .collect::<JsonResult<Vec<_>>>()?;
+            if items.is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(Value::Array(items)))
+        }
+        FieldAttribute::Map(key_type) => {
+            let dict = value
+                .downcast::<PyDict>()
+                .map_err(|_| JsonError::TypeMismatch(field.name.to_string()))?;
+            if dict.is_empty() {
+                return Ok(None);
+            }
+            let mut obj = Map::with_capacity(dict.len());
+            for (key, value) in dict.iter() {
+                let key = map_key_to_json(key_type, key)?;
+                obj.insert(key, value_to_json(&field.value_type, value)?);
+            }
+            Ok(Some(Value::Object(obj)))
+        }
+        FieldAttribute::Optional | FieldAttribute::Group(_) => {
+            if value.is_none() {
+                return Ok(None);
+            }
+            Ok(Some(value_to_json(&field.value_type, value)?))
+        }
+        FieldAttribute::None => {
+            if is_default_scalar(&field.value_type, value)? {
+                return Ok(None);
+            }
+            Ok(Some(value_to_json(&field.value_type, value)?))
+        }
+    }
+}
+
+/// Mirrors the `SKIP_DEFAULT` behaviour of the binary encoder: proto3 JSON
+/// omits a non-optional, non-repeated field that is still at its default.
+fn is_default_scalar(proto_type: &ProtoType, value: &PyAny) -> JsonResult<bool> {
+    let is_default = match proto_type {
+        ProtoType::Bool => !value.extract::<bool>()?,
+        ProtoType::Bytes => value.extract::<Vec<u8>>()?.is_empty(),
+        ProtoType::Double | ProtoType::Float => value.extract::<f64>()? == 0.0,
+        ProtoType::Int32
+        | ProtoType::Int64
+        | ProtoType::Sint32
+        | ProtoType::Sint64
+        | ProtoType::Fixed32
+        | ProtoType::Sfixed32
+        | ProtoType::Sfixed64 => value.extract::<i64>()? == 0,
+        // fixed64/uint64 are unsigned everywhere else in this codebase; an
+        // i64 extract would overflow for any value above i64::MAX.
+        ProtoType::Uint32 | ProtoType::Uint64 | ProtoType::Fixed64 => {
+            value.extract::<u64>()? == 0
+        }
+        ProtoType::String => value.extract::<&str>()?.is_empty(),
+        // Mirrors the binary encoder's encoded_len() == 0 default-check for
+        // these same two types in encode/message.rs.
+        ProtoType::Timestamp => value.extract::<Timestamp>()?.encoded_len() == 0,
+        ProtoType::Duration => value.extract::<Duration>()?.encoded_len() == 0,
+        ProtoType::Enum(_) => value
+            .getattr(intern!(value.py(), "value"))
+            .unwrap_or(value)
+            .extract::<i32>()?
+            == 0,
+        // An Any decoded with an unregistered type_url comes back as a
+        // RawAny rather than a betterproto message (see decode/value.rs);
+        // check for that before assuming `value` is always the packed
+        // message, same as the binary encoder does in encode/message.rs.
+        ProtoType::Any if value.extract::<RawAny>().is_ok() => {
+            let raw = value.extract::<RawAny>()?;
+            raw.type_url.is_empty() && raw.value.is_empty()
+        }
+        ProtoType::CustomMessage(_) | ProtoType::Any => {
+            let msg: BetterprotoMessage = value.extract()?;
+            !msg.should_be_serialized()?
+        }
+        ProtoType::Value => value.is_none(),
+        ProtoType::Struct => value.downcast::<PyDict>().is_ok_and(|d| d.is_empty()),
+        ProtoType::ListValue => value.downcast::<PyList>().is_ok_and(|l| l.is_empty()),
+        _ => false,
+    };
+    Ok(is_default)
+}
+
+fn value_to_json(proto_type: &ProtoType, value: &PyAny) -> JsonResult<Value> {
+    let py = value.py();
+    match proto_type {
+        ProtoType::Bool => Ok(Value::Bool(value.extract()?)),
+        ProtoType::Bytes => Ok(Value::String(BASE64.encode(value.extract::<Vec<u8>>()?))),
+        ProtoType::Double | ProtoType::Float => Ok(number_or_special(value.extract()?)),
+        ProtoType::Int32 | ProtoType::Sint32 | ProtoType::Fixed32 | ProtoType::Sfixed32 => {
+            Ok(Value::Number(Number::from(value.extract::<i32>()?)))
+        }
+        ProtoType::Uint32 => Ok(Value::Number(Number::from(value.extract::<u32>()?))),
+        ProtoType::Int64 | ProtoType::Sint64 | ProtoType::Sfixed64 => {
+            Ok(Value::String(value.extract::<i64>()?.to_string()))
+        }
+        // fixed64/uint64 are unsigned everywhere else in this codebase;
+        // extracting as i64 would reject any value above i64::MAX.
+        ProtoType::Uint64 | ProtoType::Fixed64 => {
+            Ok(Value::String(value.extract::<u64>()?.to_string()))
+        }
+        ProtoType::String => Ok(Value::String(value.extract()?)),
+        ProtoType::Enum(_) => Ok(enum_to_json(value)?),
+        ProtoType::CustomMessage(cls) => {
+            let msg: BetterprotoMessage = value.extract()?;
+            message_to_json(msg, cls.descriptor(py)?)
+        }
+        ProtoType::BoolValue => Ok(Value::Bool(value.extract()?)),
+        ProtoType::BytesValue => Ok(Value::String(BASE64.encode(value.extract::<Vec<u8>>()?))),
+        ProtoType::DoubleValue | ProtoType::FloatValue => Ok(number_or_special(value.extract()?)),
+        ProtoType::Int32Value => Ok(Value::Number(Number::from(value.extract::<i32>()?))),
+        ProtoType::Int64Value => Ok(Value::String(value.extract::<i64>()?.to_string())),
+        ProtoType::UInt32Value => Ok(Value::Number(Number::from(value.extract::<u32>()?))),
+        ProtoType::UInt64Value => Ok(Value::String(value.extract::<u64>()?.to_string())),
+        ProtoType::StringValue => Ok(Value::String(value.extract()?)),
+        ProtoType::Timestamp => Ok(Value::String(value.extract::<Timestamp>()?.to_rfc3339(py))),
+        ProtoType::Duration => Ok(Value::String(value.extract::<Duration>()?.to_json_string())),
+        // See the matching guard in `is_default_scalar`: an unregistered
+        // Any comes back as a RawAny, with no class to recover a schema
+        // from, so re-emit its stored type_url/value bytes directly instead
+        // of expanding a message we don't have.
+        ProtoType::Any if value.extract::<RawAny>().is_ok() => {
+            let raw = value.extract::<RawAny>()?;
+            let mut obj = Map::with_capacity(2);
+            obj.insert("@type".to_string(), Value::String(raw.type_url));
+            obj.insert("value".to_string(), Value::String(BASE64.encode(raw.value)));
+            Ok(Value::Object(obj))
+        }
+        ProtoType::Any => {
+            let msg: BetterprotoMessage = value.extract()?;
+            let inner_cls = msg.class();
+            let descriptor = inner_cls.descriptor(py)?;
+            let type_url = any_registry::type_url_for(&descriptor.full_name);
+            let mut json = message_to_json(msg, descriptor)?;
+            if let Value::Object(obj) = &mut json {
+                obj.insert("@type".to_string(), Value::String(type_url));
+            }
+            Ok(json)
+        }
+        ProtoType::Struct | ProtoType::Value | ProtoType::ListValue => dynamic_value_to_json(value),
+    }
+}
+
+/// Converts the native `dict`/`list`/scalar Python value backing a
+/// `Struct`/`Value`/`ListValue` field directly to its proto3 JSON shape,
+/// which is just that value's own JSON representation.
+fn dynamic_value_to_json(value: &PyAny) -> JsonResult<Value> {
+    let mismatch = || JsonError::TypeMismatch("google.protobuf.Value".to_string());
+    if value.is_none() {
+        return Ok(Value::Null);
+    }
+    if let Ok(b) = value.downcast::<PyBool>() {
+        return Ok(Value::Bool(b.is_true()));
+    }
+    if let Ok(n) = value.extract::<f64>() {
+        return Ok(number_or_special(n));
+    }
+    if let Ok(s) = value.extract::<&str>() {
+        return Ok(Value::String(s.to_string()));
+    }
+    if let Ok(list) = value.downcast::<PyList>() {
+        let items = list
+            .iter()
+            .map(dynamic_value_to_json)
+            .collect::<JsonResult<Vec<_>>>()?;
+        return Ok(Value::Array(items));
+    }
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        let mut obj = Map::with_capacity(dict.len());
+        for (key, value) in dict.iter() {
+            obj.insert(key.extract::<String>().map_err(|_| mismatch())?, dynamic_value_to_json(value)?);
+        }
+        return Ok(Value::Object(obj));
+    }
+    Err(mismatch())
+}
+
+fn enum_to_json(value: &PyAny) -> JsonResult<Value> {
+    let py = value.py();
+    match value.getattr(intern!(py, "name")) {
+        Ok(name) => Ok(Value::String(name.extract()?)),
+        Err(_) => {
+            let raw = value
+                .getattr(intern!(py, "value"))
+                .unwrap_or(value)
+                .extract::<i32>()?;
+            Ok(Value::Number(Number::from(raw)))
+        }
+    }
+}
+
+fn number_or_special(value: f64) -> Value {
+    if value.is_nan() {
+        Value::String("NaN".to_string())
+    } else if value.is_infinite() {
+        Value::String(if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string())
+    } else {
+        Number::from_f64(value)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
+
+fn map_key_to_json(key_type: &ProtoType, key: &PyAny) -> JsonResult<String> {
+    let key = match key_type {
+        ProtoType::Bool => key.extract::<bool>()?.to_string(),
+        ProtoType::String => key.extract::<String>()?,
+        _ => key.str()?.extract::<String>()?,
+    };
+    Ok(key)
+}
@@ -0,0 +1,29 @@
+use crate::{betterproto_interop::InteropError, decode::DecodeError, encode::EncodeError};
+use pyo3::{exceptions::PyRuntimeError, PyErr};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum JsonError {
+    #[error(transparent)]
+    Interop(#[from] InteropError),
+    #[error(transparent)]
+    Encode(#[from] EncodeError),
+    #[error(transparent)]
+    Decode(#[from] DecodeError),
+    #[error("Given object is not a valid betterproto message.")]
+    NoBetterprotoMessage(#[from] PyErr),
+    #[error("Invalid JSON was given: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+    #[error("A JSON value does not match the expected shape for `{0}`.")]
+    TypeMismatch(String),
+    #[error("Map key `{0}` is not a valid value for the map's key type.")]
+    InvalidMapKey(String),
+}
+
+pub type JsonResult<T> = Result<T, JsonError>;
+
+impl From<JsonError> for PyErr {
+    fn from(value: JsonError) -> Self {
+        PyRuntimeError::new_err(value.to_string())
+    }
+}
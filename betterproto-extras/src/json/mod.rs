@@ -0,0 +1,6 @@
+mod case;
+mod decode;
+mod encode;
+mod error;
+
+pub use self::{decode::merge_json_into_message, encode::message_to_json, error::JsonResult};
@@ -1,20 +1,30 @@
+mod any_registry;
 mod betterproto_interop;
 mod decode;
+mod delimited;
 mod descriptors;
 mod encode;
+mod json;
 mod well_known_types;
 
-use betterproto_interop::BetterprotoMessage;
-use decode::{merge_into_message, DecodeResult};
+use any_registry::register_any_type;
+use betterproto_interop::{BetterprotoMessage, BetterprotoMessageClass};
+use decode::{merge_into_message_validated, DecodeResult};
 use encode::{EncodeResult, MessageEncoder};
+use json::{merge_json_into_message, message_to_json, JsonResult};
 use pyo3::{prelude::*, types::PyBytes};
 use std::sync::Arc;
+use well_known_types::RawAny;
 
 pub type Str = Arc<str>;
 
+/// Decodes `buf` into `obj`. When `validate` is set, rejects a message that
+/// sets more than one member of the same oneof instead of silently keeping
+/// only the last one seen, turning the decoder into a strict parser.
 #[pyfunction]
-fn deserialize(obj: BetterprotoMessage, mut buf: &[u8]) -> DecodeResult<()> {
-    merge_into_message(obj, &mut buf)
+#[pyo3(signature = (obj, buf, validate = false))]
+fn deserialize(obj: BetterprotoMessage, mut buf: &[u8], validate: bool) -> DecodeResult<()> {
+    merge_into_message_validated(obj, &mut buf, validate)
 }
 
 #[pyfunction]
@@ -24,9 +34,69 @@ fn serialize<'py>(py: Python<'py>, msg: BetterprotoMessage) -> EncodeResult<&'py
     Ok(PyBytes::new(py, &encoder.into_vec()))
 }
 
+/// Like `serialize`, but byte-stable: fields are emitted in ascending tag
+/// order and map entries are sorted by key, so equal messages always
+/// produce equal bytes. Suitable for signing, hashing, and caching keyed on
+/// the encoded output.
+#[pyfunction]
+fn serialize_canonical<'py>(py: Python<'py>, msg: BetterprotoMessage) -> EncodeResult<&'py PyBytes> {
+    let cls = msg.class();
+    let encoder = MessageEncoder::from_betterproto_msg_canonical(msg, cls.descriptor(py)?)?;
+    Ok(PyBytes::new(py, &encoder.into_vec()))
+}
+
+/// Decodes the canonical proto3 JSON mapping directly into `obj`, without
+/// going through betterproto's Python `from_dict`/`from_json`.
+#[pyfunction]
+fn deserialize_json(obj: BetterprotoMessage, data: &str) -> JsonResult<()> {
+    let value = serde_json::from_str(data)?;
+    merge_json_into_message(obj, &value)
+}
+
+/// Encodes `msg` using the canonical proto3 JSON mapping, omitting default
+/// field values just like the binary `serialize` path does.
+#[pyfunction]
+fn serialize_json(py: Python, msg: BetterprotoMessage) -> JsonResult<String> {
+    let cls = msg.class();
+    let value = message_to_json(msg, cls.descriptor(py)?)?;
+    Ok(value.to_string())
+}
+
+/// Decodes a stream of length-delimited `cls` messages from `source`, which
+/// may be raw bytes or a file-like object exposing `read`. Each record is
+/// framed as a varint byte length followed by that many bytes of an encoded
+/// message, the same framing `writeDelimitedTo`/`parseDelimitedFrom` use. A
+/// trailing record cut short by a partial length prefix or a truncated body
+/// raises an error rather than being silently dropped.
+#[pyfunction]
+fn deserialize_delimited(
+    py: Python,
+    cls: BetterprotoMessageClass,
+    source: &PyAny,
+) -> DecodeResult<Vec<PyObject>> {
+    delimited::deserialize_delimited(py, cls, source)
+}
+
+/// Encodes each message in `messages` with a varint length prefix into one
+/// `PyBytes`, the inverse of `deserialize_delimited`.
+#[pyfunction]
+fn serialize_delimited<'py>(
+    py: Python<'py>,
+    messages: Vec<BetterprotoMessage>,
+) -> EncodeResult<&'py PyBytes> {
+    delimited::serialize_delimited(py, messages)
+}
+
 #[pymodule]
 fn betterproto_extras(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(deserialize, m)?)?;
     m.add_function(wrap_pyfunction!(serialize, m)?)?;
+    m.add_function(wrap_pyfunction!(serialize_canonical, m)?)?;
+    m.add_function(wrap_pyfunction!(deserialize_json, m)?)?;
+    m.add_function(wrap_pyfunction!(serialize_json, m)?)?;
+    m.add_function(wrap_pyfunction!(deserialize_delimited, m)?)?;
+    m.add_function(wrap_pyfunction!(serialize_delimited, m)?)?;
+    m.add_function(wrap_pyfunction!(register_any_type, m)?)?;
+    m.add_class::<RawAny>()?;
     Ok(())
 }
@@ -1,10 +1,43 @@
 use indoc::indoc;
 use prost::Message;
 use pyo3::{
+    exceptions::PyTypeError,
+    pyclass, pymethods,
     sync::GILOnceCell,
-    types::{PyBytes, PyModule},
-    FromPyObject, PyAny, PyObject, PyResult, Python, ToPyObject,
+    types::{PyBool, PyBytes, PyDict, PyList, PyModule},
+    FromPyObject, IntoPy, PyAny, PyObject, PyResult, Python, ToPyObject,
 };
+use std::collections::BTreeMap;
+
+/// The wire shape of `google.protobuf.Any`: a type URL plus the packed
+/// message's serialized bytes. Exposed to Python so unregistered type URLs
+/// can still be inspected instead of silently dropped.
+#[pyclass(name = "Any")]
+#[derive(Message, Clone, Default)]
+pub struct RawAny {
+    #[pyo3(get)]
+    #[prost(string, tag = "1")]
+    pub type_url: String,
+    #[pyo3(get)]
+    #[prost(bytes, tag = "2")]
+    pub value: Vec<u8>,
+}
+
+#[pymethods]
+impl RawAny {
+    #[new]
+    fn new(type_url: String, value: Vec<u8>) -> Self {
+        Self { type_url, value }
+    }
+}
+
+impl ToPyObject for RawAny {
+    fn to_object(&self, py: Python) -> PyObject {
+        pyo3::Py::new(py, self.clone())
+            .expect("constructing a plain pyclass instance will not fail")
+            .to_object(py)
+    }
+}
 
 #[derive(Message)]
 pub struct BoolValue {
@@ -76,6 +109,180 @@ pub struct Timestamp {
     pub nanos: i32,
 }
 
+/// `google.protobuf.Struct`: a `map<string, Value>`. betterproto represents
+/// this as a plain `dict`, so `fields` only ever needs to round-trip through
+/// the wire format, never surface to Python directly.
+#[derive(Message, Clone, Default)]
+pub struct Struct {
+    #[prost(map = "string, message", tag = "1")]
+    pub fields: BTreeMap<String, Value>,
+}
+
+/// `google.protobuf.ListValue`: `repeated Value`, mapping to a plain `list`.
+#[derive(Message, Clone, Default)]
+pub struct ListValue {
+    #[prost(message, repeated, tag = "1")]
+    pub values: Vec<Value>,
+}
+
+/// `google.protobuf.Value`: a oneof over null/number/string/bool/`Struct`/
+/// `ListValue`, mapping to `None`/`float`/`str`/`bool`/`dict`/`list`. The
+/// `NULL_VALUE` enum only has one member, so it's encoded as a plain `int32`
+/// rather than a full `ProtoType::Enum`.
+#[derive(Message, Clone, Default)]
+pub struct Value {
+    #[prost(oneof = "value::Kind", tags = "1, 2, 3, 4, 5, 6")]
+    pub kind: Option<value::Kind>,
+}
+
+pub mod value {
+    use super::{ListValue, Struct};
+
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Kind {
+        #[prost(int32, tag = "1")]
+        NullValue(i32),
+        #[prost(double, tag = "2")]
+        NumberValue(f64),
+        #[prost(string, tag = "3")]
+        StringValue(String),
+        #[prost(bool, tag = "4")]
+        BoolValue(bool),
+        #[prost(message, tag = "5")]
+        StructValue(Struct),
+        #[prost(message, tag = "6")]
+        ListValue(ListValue),
+    }
+}
+
+impl Duration {
+    /// Renders the canonical proto3 JSON form, e.g. `"3.000000100s"`.
+    ///
+    /// Fractional digits are printed at 0/3/6/9 granularity, trimmed to the
+    /// narrowest width that represents `nanos` exactly.
+    pub fn to_json_string(&self) -> String {
+        let sign = if self.seconds < 0 || self.nanos < 0 {
+            "-"
+        } else {
+            ""
+        };
+        let seconds = self.seconds.unsigned_abs();
+        let nanos = self.nanos.unsigned_abs();
+        let fraction = if nanos == 0 {
+            String::new()
+        } else if nanos % 1_000_000 == 0 {
+            format!(".{:03}", nanos / 1_000_000)
+        } else if nanos % 1_000 == 0 {
+            format!(".{:06}", nanos / 1_000)
+        } else {
+            format!(".{nanos:09}")
+        };
+        format!("{sign}{seconds}{fraction}s")
+    }
+
+    /// Parses the canonical proto3 JSON form, e.g. `"3.000000100s"`.
+    pub fn from_json_string(s: &str) -> Result<Self, ()> {
+        let s = s.strip_suffix('s').ok_or(())?;
+        let (whole, fraction) = match s.split_once('.') {
+            Some((whole, fraction)) => (whole, fraction),
+            None => (s, ""),
+        };
+        let negative = whole.starts_with('-');
+        let whole: i64 = whole.parse().map_err(|_| ())?;
+        let nanos: i32 = if fraction.is_empty() {
+            0
+        } else {
+            let padded = format!("{fraction:0<9}");
+            padded.get(..9).ok_or(())?.parse().map_err(|_| ())?
+        };
+        let nanos = if negative { -nanos } else { nanos };
+        Ok(Duration {
+            seconds: whole,
+            nanos,
+        })
+    }
+}
+
+impl Timestamp {
+    /// Renders the canonical proto3 JSON form, an RFC 3339 string ending in
+    /// `Z` with fractional seconds printed at 0/3/6/9 digit granularity.
+    ///
+    /// Delegates the calendar math to Python's `datetime`, matching how the
+    /// rest of this type converts to/from Python.
+    pub fn to_rfc3339(&self, py: Python) -> String {
+        static FORMATTER_CACHE: GILOnceCell<PyObject> = GILOnceCell::new();
+        let formatter = FORMATTER_CACHE
+            .get_or_init(py, || {
+                PyModule::from_code(
+                    py,
+                    indoc! {"
+                        from datetime import datetime, timezone
+
+                        def formatter(seconds, nanos):
+                            dt = datetime.fromtimestamp(seconds, tz=timezone.utc)
+                            if nanos == 0:
+                                frac = ''
+                            elif nanos % 1_000_000 == 0:
+                                frac = f'.{nanos // 1_000_000:03d}'
+                            elif nanos % 1_000 == 0:
+                                frac = f'.{nanos // 1_000:06d}'
+                            else:
+                                frac = f'.{nanos:09d}'
+                            return dt.strftime('%Y-%m-%dT%H:%M:%S') + frac + 'Z'
+                    "},
+                    "",
+                    "",
+                )
+                .expect("This is a valid Python module")
+                .getattr("formatter")
+                .expect("Attribute exists")
+                .to_object(py)
+            })
+            .as_ref(py);
+        formatter
+            .call1((self.seconds, self.nanos))
+            .expect("static function will not fail")
+            .extract()
+            .expect("formatter returns a str")
+    }
+
+    /// Parses an RFC 3339 string as produced by [`Timestamp::to_rfc3339`].
+    ///
+    /// Delegates to Python's `datetime.fromisoformat`, so sub-microsecond
+    /// fractional digits are truncated to the precision `datetime` supports.
+    pub fn from_rfc3339(py: Python, s: &str) -> PyResult<Self> {
+        static PARSER_CACHE: GILOnceCell<PyObject> = GILOnceCell::new();
+        let parser = PARSER_CACHE
+            .get_or_init(py, || {
+                PyModule::from_code(
+                    py,
+                    indoc! {"
+                        import calendar
+                        from datetime import timezone
+                        from datetime import datetime as _datetime
+
+                        def parser(s):
+                            if s.endswith('Z'):
+                                s = s[:-1] + '+00:00'
+                            dt = _datetime.fromisoformat(s).astimezone(timezone.utc)
+                            seconds = calendar.timegm(dt.utctimetuple())
+                            nanos = dt.microsecond * 1_000
+                            return (seconds, nanos)
+                    "},
+                    "",
+                    "",
+                )
+                .expect("This is a valid Python module")
+                .getattr("parser")
+                .expect("Attribute exists")
+                .to_object(py)
+            })
+            .as_ref(py);
+        let (seconds, nanos) = parser.call1((s,))?.extract()?;
+        Ok(Timestamp { seconds, nanos })
+    }
+}
+
 impl<'py> FromPyObject<'py> for BoolValue {
     fn extract(ob: &'py PyAny) -> PyResult<Self> {
         let res = BoolValue {
@@ -157,7 +364,62 @@ impl<'py> FromPyObject<'py> for StringValue {
     }
 }
 
+impl<'py> FromPyObject<'py> for Value {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        let kind = if ob.is_none() {
+            None
+        } else if let Ok(b) = ob.downcast::<PyBool>() {
+            Some(value::Kind::BoolValue(b.is_true()))
+        } else if let Ok(n) = ob.extract::<f64>() {
+            Some(value::Kind::NumberValue(n))
+        } else if let Ok(s) = ob.extract::<String>() {
+            Some(value::Kind::StringValue(s))
+        } else if let Ok(dict) = ob.downcast::<PyDict>() {
+            Some(value::Kind::StructValue(Struct::extract(dict)?))
+        } else if ob.iter().is_ok() {
+            // Accepts any iterable (list, tuple, generator, ...), not just
+            // `list`, so callers don't need to coerce their own sequences.
+            Some(value::Kind::ListValue(ListValue::extract(ob)?))
+        } else {
+            return Err(PyTypeError::new_err(format!(
+                "{} is not a valid google.protobuf.Value (expected None, bool, \
+                 float, str, dict, or an iterable)",
+                ob.get_type().name()?
+            )));
+        };
+        Ok(Value { kind })
+    }
+}
+
+impl<'py> FromPyObject<'py> for Struct {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        let dict = ob.downcast::<PyDict>()?;
+        let mut fields = BTreeMap::new();
+        for (key, value) in dict.iter() {
+            fields.insert(key.extract::<String>()?, value.extract::<Value>()?);
+        }
+        Ok(Struct { fields })
+    }
+}
+
+impl<'py> FromPyObject<'py> for ListValue {
+    fn extract(ob: &'py PyAny) -> PyResult<Self> {
+        let values = ob
+            .iter()?
+            .map(|item| Value::extract(item?))
+            .collect::<PyResult<_>>()?;
+        Ok(ListValue { values })
+    }
+}
+
 impl<'py> FromPyObject<'py> for Duration {
+    /// Carries `total_us` (the exact integer microsecond count `timedelta`
+    /// already stores) across the boundary, then splits it into
+    /// `seconds`/`nanos` with integer arithmetic only, truncating toward
+    /// zero so the two fields share a sign as the spec requires. Sub-
+    /// microsecond precision can't exist on a `timedelta` to begin with, so
+    /// no truncation happens here beyond what Python's `datetime` already
+    /// imposed on construction.
     fn extract(ob: &'py PyAny) -> PyResult<Self> {
         let py = ob.py();
         static DECONSTRUCTOR_CACHE: GILOnceCell<PyObject> = GILOnceCell::new();
@@ -167,11 +429,16 @@ impl<'py> FromPyObject<'py> for Duration {
                     py,
                     indoc! {"
                         from datetime import timedelta
-                        
+
                         def deconstructor(delta, *, _1_microsecond = timedelta(microseconds=1)):
-                            total_ms = delta // _1_microsecond
-                            seconds = int(total_ms / 1e6)
-                            nanos = int((total_ms % 1e6) * 1e3)
+                            total_us = delta // _1_microsecond
+                            # Truncate toward zero (not Python's floor //) so
+                            # `seconds` and `nanos` share a sign.
+                            if total_us < 0:
+                                seconds = -((-total_us) // 1_000_000)
+                            else:
+                                seconds = total_us // 1_000_000
+                            nanos = (total_us - seconds * 1_000_000) * 1_000
                             return (seconds, nanos)
                     "},
                     "",
@@ -190,6 +457,11 @@ impl<'py> FromPyObject<'py> for Duration {
 }
 
 impl<'py> FromPyObject<'py> for Timestamp {
+    /// Reads `seconds` via `calendar.timegm`, which works entirely in
+    /// integers, instead of `dt.timestamp()`, which round-trips through a
+    /// float and loses precision for dates far from the epoch. `dt` is
+    /// treated as UTC (via `utctimetuple`), matching the `tz=timezone.utc`
+    /// datetimes this module always produces.
     fn extract(ob: &'py PyAny) -> PyResult<Self> {
         let py = ob.py();
         static DECONSTRUCTOR_CACHE: GILOnceCell<PyObject> = GILOnceCell::new();
@@ -198,9 +470,11 @@ impl<'py> FromPyObject<'py> for Timestamp {
                 PyModule::from_code(
                     py,
                     indoc! {"
+                        import calendar
+
                         def deconstructor(dt):
-                            seconds = int(dt.timestamp())
-                            nanos = int(dt.microsecond * 1e3)
+                            seconds = calendar.timegm(dt.utctimetuple())
+                            nanos = dt.microsecond * 1_000
                             return (seconds, nanos)
                     "},
                     "",
@@ -272,7 +546,48 @@ impl ToPyObject for StringValue {
     }
 }
 
+impl ToPyObject for Value {
+    fn to_object(&self, py: Python) -> PyObject {
+        match &self.kind {
+            None | Some(value::Kind::NullValue(_)) => py.None(),
+            Some(value::Kind::NumberValue(n)) => n.to_object(py),
+            Some(value::Kind::StringValue(s)) => s.to_object(py),
+            Some(value::Kind::BoolValue(b)) => b.to_object(py),
+            Some(value::Kind::StructValue(s)) => s.to_object(py),
+            Some(value::Kind::ListValue(l)) => l.to_object(py),
+        }
+    }
+}
+
+impl ToPyObject for Struct {
+    fn to_object(&self, py: Python) -> PyObject {
+        let dict = PyDict::new(py);
+        for (key, value) in self.fields.iter() {
+            dict.set_item(key, value.to_object(py))
+                .expect("setting a dict item with a str key will not fail");
+        }
+        dict.into_py(py)
+    }
+}
+
+impl ToPyObject for ListValue {
+    fn to_object(&self, py: Python) -> PyObject {
+        let values = self
+            .values
+            .iter()
+            .map(|v| v.to_object(py))
+            .collect::<Vec<_>>();
+        values.to_object(py)
+    }
+}
+
 impl ToPyObject for Duration {
+    /// Builds the `timedelta` from integer `seconds`/`microseconds` rather
+    /// than collapsing them into a single float first, so large second
+    /// counts don't lose precision. `nanos` is truncated toward zero in
+    /// Rust before crossing into Python — Python's `//` floors, which would
+    /// shift a negative sub-microsecond remainder (e.g. `nanos = -1`) a
+    /// whole microsecond away from the correct, sign-matching truncation.
     fn to_object(&self, py: Python) -> PyObject {
         static CONSTRUCTOR_CACHE: GILOnceCell<PyObject> = GILOnceCell::new();
         let constructor = CONSTRUCTOR_CACHE.get_or_init(py, || {
@@ -280,9 +595,9 @@ impl ToPyObject for Duration {
                 py,
                 indoc! {"
                     from datetime import timedelta
-                    
-                    def constructor(s, ms):
-                        return timedelta(seconds=s, microseconds=ms)
+
+                    def constructor(seconds, microseconds):
+                        return timedelta(seconds=seconds, microseconds=microseconds)
                 "},
                 "",
                 "",
@@ -292,23 +607,32 @@ impl ToPyObject for Duration {
             .expect("Attribute exists")
             .to_object(py)
         });
+        let microseconds = self.nanos / 1_000;
         constructor
-            .call1(py, (self.seconds as f64, (self.nanos as f64) / 1e3))
+            .call1(py, (self.seconds, microseconds))
             .expect("static function will not fail")
     }
 }
 
 impl ToPyObject for Timestamp {
+    /// Builds the `datetime` from integer `seconds` and a `timedelta` of the
+    /// sub-second remainder instead of summing them into one float first,
+    /// which loses precision for timestamps far from the epoch. `nanos` is
+    /// truncated toward zero to microseconds in Rust before crossing into
+    /// Python, rather than relying on Python's floor `//` (which would shift
+    /// a negative sub-microsecond remainder a whole microsecond off).
     fn to_object(&self, py: Python) -> PyObject {
         static CONSTRUCTOR_CACHE: GILOnceCell<PyObject> = GILOnceCell::new();
         let constructor = CONSTRUCTOR_CACHE.get_or_init(py, || {
             PyModule::from_code(
                 py,
                 indoc! {"
-                    from datetime import datetime, timezone
-                    
-                    def constructor(ts):
-                        return datetime.fromtimestamp(ts, tz=timezone.utc)
+                    from datetime import datetime, timedelta, timezone
+
+                    def constructor(seconds, microseconds):
+                        return datetime.fromtimestamp(seconds, tz=timezone.utc) + timedelta(
+                            microseconds=microseconds
+                        )
                 "},
                 "",
                 "",
@@ -318,9 +642,9 @@ impl ToPyObject for Timestamp {
             .expect("Attribute exists")
             .to_object(py)
         });
-        let ts = (self.seconds as f64) + (self.nanos as f64) / 1e9;
+        let microseconds = self.nanos / 1_000;
         constructor
-            .call1(py, (ts,))
+            .call1(py, (self.seconds, microseconds))
             .expect("static function will not fail")
     }
 }